@@ -2,25 +2,124 @@
 //!
 //! Handles file uploads to S3 for the Glitch website.
 
+use std::time::Duration;
+
+use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::Client as S3Client;
+use glitch_core::GlitchError;
 use lambda_http::{run, service_fn, Body, Error, Request, Response};
+use serde::{Deserialize, Serialize};
 use tracing_subscriber::EnvFilter;
+use uuid::Uuid;
+
+/// Content types we'll issue upload URLs for.
+const ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "video/mp4",
+];
+
+/// Largest upload we'll issue a presigned URL for, in bytes (100 MiB).
+const MAX_UPLOAD_BYTES: u64 = 100 * 1024 * 1024;
+
+/// How long a presigned PUT URL remains valid for.
+const PRESIGN_EXPIRY: Duration = Duration::from_secs(300);
 
 /// Handler state initialized once at Lambda cold start
 struct AppState {
-    #[allow(dead_code)]
     s3: S3Client,
+    bucket: String,
 }
 
-async fn handler(_state: &AppState, _event: Request) -> Result<Response<Body>, Error> {
-    // Placeholder implementation
-    let resp = Response::builder()
-        .status(200)
-        .header("content-type", "application/json")
-        .body(Body::from(r#"{"message": "media-upload lambda ready"}"#))
-        .map_err(Box::new)?;
+/// Incoming upload request body
+#[derive(Debug, Deserialize)]
+struct UploadRequest {
+    filename: String,
+    content_type: String,
+    size: u64,
+}
+
+/// Response returned once a presigned URL has been issued
+#[derive(Debug, Serialize)]
+struct UploadResponse {
+    upload_url: String,
+    object_url: String,
+    key: String,
+}
+
+/// Validates an upload request against our content-type allow-list and size limit.
+fn validate(request: &UploadRequest) -> glitch_core::Result<()> {
+    if !ALLOWED_CONTENT_TYPES.contains(&request.content_type.as_str()) {
+        return Err(GlitchError::validation(format!(
+            "unsupported content type: {}",
+            request.content_type
+        )));
+    }
+    if request.size == 0 {
+        return Err(GlitchError::validation("file size must be greater than 0 bytes"));
+    }
+    if request.size > MAX_UPLOAD_BYTES {
+        return Err(GlitchError::validation(format!(
+            "file size {} exceeds limit of {MAX_UPLOAD_BYTES} bytes",
+            request.size
+        )));
+    }
+    Ok(())
+}
+
+/// Builds a unique S3 object key, preserving the original file extension.
+fn object_key(filename: &str) -> String {
+    match std::path::Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some(ext) => format!("uploads/{}.{ext}", Uuid::new_v4()),
+        None => format!("uploads/{}", Uuid::new_v4()),
+    }
+}
+
+async fn handle_upload(state: &AppState, event: &Request) -> glitch_core::Result<UploadResponse> {
+    let request: UploadRequest = serde_json::from_slice(event.body())?;
+    validate(&request)?;
+
+    let key = object_key(&request.filename);
+    let presigning_config = PresigningConfig::expires_in(PRESIGN_EXPIRY)
+        .map_err(|err| GlitchError::Internal(err.to_string()))?;
 
-    Ok(resp)
+    let presigned = state
+        .s3
+        .put_object()
+        .bucket(&state.bucket)
+        .key(&key)
+        .content_type(&request.content_type)
+        .presigned(presigning_config)
+        .await?;
+
+    Ok(UploadResponse {
+        upload_url: presigned.uri().to_string(),
+        object_url: format!("https://{}.s3.amazonaws.com/{key}", state.bucket),
+        key,
+    })
+}
+
+async fn handler(state: &AppState, event: Request) -> Result<Response<Body>, Error> {
+    match handle_upload(state, &event).await {
+        Ok(response) => Ok(Response::builder()
+            .status(200)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&response)?))
+            .map_err(Box::new)?),
+        Err(err) => {
+            err.trace();
+            Ok(Response::builder()
+                .status(err.status_code())
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&err.to_response_body())?))
+                .map_err(Box::new)?)
+        }
+    }
 }
 
 #[tokio::main]
@@ -34,7 +133,63 @@ async fn main() -> Result<(), Error> {
     let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
     let state = AppState {
         s3: S3Client::new(&config),
+        bucket: std::env::var("UPLOAD_BUCKET").expect("UPLOAD_BUCKET must be set"),
     };
 
     run(service_fn(|event| handler(&state, event))).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(content_type: &str, size: u64) -> UploadRequest {
+        UploadRequest {
+            filename: "photo.png".to_string(),
+            content_type: content_type.to_string(),
+            size,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_an_allowed_content_type_and_size() {
+        assert!(validate(&request("image/png", 1024)).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_a_disallowed_content_type() {
+        let err = validate(&request("application/x-sh", 1024)).unwrap_err();
+        assert_eq!(err.status_code(), 400);
+    }
+
+    #[test]
+    fn validate_rejects_a_zero_byte_upload() {
+        let err = validate(&request("image/png", 0)).unwrap_err();
+        assert_eq!(err.status_code(), 400);
+    }
+
+    #[test]
+    fn validate_rejects_an_upload_over_the_size_limit() {
+        let err = validate(&request("image/png", MAX_UPLOAD_BYTES + 1)).unwrap_err();
+        assert_eq!(err.status_code(), 400);
+    }
+
+    #[test]
+    fn object_key_preserves_the_extension() {
+        let key = object_key("holiday-photo.JPG");
+        assert!(key.starts_with("uploads/"));
+        assert!(key.ends_with(".JPG"));
+    }
+
+    #[test]
+    fn object_key_is_unique_per_call() {
+        assert_ne!(object_key("a.png"), object_key("a.png"));
+    }
+
+    #[test]
+    fn object_key_handles_a_filename_without_an_extension() {
+        let key = object_key("no-extension");
+        assert!(key.starts_with("uploads/"));
+        assert!(!key.contains('.'));
+    }
+}
@@ -0,0 +1,560 @@
+//! Error Core Library
+//!
+//! `StudioError` and `GlitchError` are the same error shape used by two
+//! different backend product lines. Rather than maintain two
+//! byte-for-byte-identical enums, this crate defines that shape once as a
+//! declarative macro and each backend's core crate invokes it to generate
+//! its own named type. The same reasoning applies to the retry helper in
+//! [`retry`]: it lives here once, generic over [`retry::RetryableError`],
+//! rather than being copy-pasted per backend.
+
+pub mod retry;
+
+/// Generates a service error enum named `$name` with the full set of
+/// variants, AWS `SdkError` conversions, and helper methods shared by every
+/// backend in this workspace.
+///
+/// Invoke this once per backend core crate, inside that crate's `error`
+/// module:
+///
+/// ```ignore
+/// error_core::define_service_error!(StudioError);
+/// ```
+#[macro_export]
+macro_rules! define_service_error {
+    ($name:ident) => {
+        /// Main error type for this service
+        #[derive(::thiserror::Error, ::std::fmt::Debug)]
+        pub enum $name {
+            /// Configuration error
+            #[error("configuration error: {0}")]
+            Config(::std::string::String),
+
+            /// Validation error
+            #[error("validation error: {message}")]
+            Validation {
+                message: ::std::string::String,
+                code: ::std::option::Option<::std::string::String>,
+                request_id: ::std::option::Option<::std::string::String>,
+                #[source]
+                source: ::std::option::Option<::std::boxed::Box<dyn ::std::error::Error + Send + Sync>>,
+            },
+
+            /// Not found error
+            #[error("not found: {message}")]
+            NotFound {
+                message: ::std::string::String,
+                code: ::std::option::Option<::std::string::String>,
+                request_id: ::std::option::Option<::std::string::String>,
+                #[source]
+                source: ::std::option::Option<::std::boxed::Box<dyn ::std::error::Error + Send + Sync>>,
+            },
+
+            /// Internal error. Covers deterministic, non-AWS failures (a bad
+            /// config value, a bug in our own code); retrying one of these
+            /// without changing anything will fail the same way, so it is
+            /// never classified as retryable. An AWS-reported transient fault
+            /// should use `Unavailable` instead.
+            #[error("internal error: {0}")]
+            Internal(::std::string::String),
+
+            /// Request was throttled by an AWS service
+            #[error("throttled: {message}")]
+            Throttled {
+                message: ::std::string::String,
+                code: ::std::option::Option<::std::string::String>,
+                request_id: ::std::option::Option<::std::string::String>,
+                #[source]
+                source: ::std::option::Option<::std::boxed::Box<dyn ::std::error::Error + Send + Sync>>,
+            },
+
+            /// Request conflicted with the current state of a resource
+            #[error("conflict: {message}")]
+            Conflict {
+                message: ::std::string::String,
+                code: ::std::option::Option<::std::string::String>,
+                request_id: ::std::option::Option<::std::string::String>,
+                #[source]
+                source: ::std::option::Option<::std::boxed::Box<dyn ::std::error::Error + Send + Sync>>,
+            },
+
+            /// Caller's credentials were rejected by an AWS service
+            #[error("access denied: {message}")]
+            AccessDenied {
+                message: ::std::string::String,
+                code: ::std::option::Option<::std::string::String>,
+                request_id: ::std::option::Option<::std::string::String>,
+                #[source]
+                source: ::std::option::Option<::std::boxed::Box<dyn ::std::error::Error + Send + Sync>>,
+            },
+
+            /// An AWS service quota was exceeded
+            #[error("quota exceeded: {message}")]
+            QuotaExceeded {
+                message: ::std::string::String,
+                code: ::std::option::Option<::std::string::String>,
+                request_id: ::std::option::Option<::std::string::String>,
+                #[source]
+                source: ::std::option::Option<::std::boxed::Box<dyn ::std::error::Error + Send + Sync>>,
+            },
+
+            /// An AWS service reported a transient internal fault (e.g.
+            /// `InternalServerException`). Unlike the plain `Internal` variant
+            /// above, this one is known to be transient and safe to retry.
+            #[error("service unavailable: {message}")]
+            Unavailable {
+                message: ::std::string::String,
+                code: ::std::option::Option<::std::string::String>,
+                request_id: ::std::option::Option<::std::string::String>,
+                #[source]
+                source: ::std::option::Option<::std::boxed::Box<dyn ::std::error::Error + Send + Sync>>,
+            },
+
+            /// Serialization error
+            #[error("serialization error: {0}")]
+            Serialization(#[from] ::serde_json::Error),
+
+            /// An AWS failure that doesn't match any modeled service exception:
+            /// an error code we don't recognize, a malformed response, or a
+            /// connection/timeout failure. Mirrors the `Unhandled` arm smithy
+            /// generates for every operation error, so a conversion never has
+            /// to silently drop the original failure.
+            #[error("unhandled aws error: {message}")]
+            Unhandled {
+                message: ::std::string::String,
+                code: ::std::option::Option<::std::string::String>,
+                request_id: ::std::option::Option<::std::string::String>,
+                #[source]
+                source: ::std::option::Option<::std::boxed::Box<dyn ::std::error::Error + Send + Sync>>,
+            },
+        }
+
+        /// Whether retrying the operation that produced an error stands a
+        /// chance of succeeding.
+        #[derive(::std::fmt::Debug, ::std::clone::Clone, ::std::marker::Copy, PartialEq, Eq)]
+        pub enum Retryability {
+            /// The failure was transient (throttling, a momentary service
+            /// fault); a retry after backing off may succeed.
+            Retryable,
+            /// The failure is inherent to the request (validation, access, a
+            /// missing resource); retrying without changing the request will
+            /// fail the same way.
+            NotRetryable,
+        }
+
+        /// Classification of a modeled AWS service error, derived from its error code.
+        enum AwsErrorKind {
+            Throttled,
+            Conflict,
+            AccessDenied,
+            QuotaExceeded,
+            NotFound,
+            Validation,
+            Internal,
+        }
+
+        /// Classifies a smithy error code into one of our structured kinds, if recognized.
+        ///
+        /// Covers both DynamoDB's and S3's error codes. The two services don't
+        /// share a vocabulary for the same failure, and S3's REST API in
+        /// particular doesn't use the `*Exception` suffix DynamoDB's
+        /// JSON-protocol codes do (e.g. DynamoDB's `ThrottlingException` vs.
+        /// S3's `SlowDown`; `AccessDeniedException` vs. plain `AccessDenied`),
+        /// so each structured kind lists every service-specific code we know
+        /// maps to it.
+        fn classify_aws_error_code(code: ::std::option::Option<&str>) -> ::std::option::Option<AwsErrorKind> {
+            match code? {
+                "ThrottlingException" | "ProvisionedThroughputExceededException" | "RequestLimitExceeded"
+                | "SlowDown" => ::std::option::Option::Some(AwsErrorKind::Throttled),
+                "ConflictException" | "ConditionalCheckFailedException" | "TransactionConflictException" => {
+                    ::std::option::Option::Some(AwsErrorKind::Conflict)
+                }
+                "AccessDeniedException" | "AccessDenied" => ::std::option::Option::Some(AwsErrorKind::AccessDenied),
+                "ServiceQuotaExceededException" => ::std::option::Option::Some(AwsErrorKind::QuotaExceeded),
+                "ResourceNotFoundException" | "ResourceNotFoundFault" | "NoSuchKey" | "NoSuchBucket" => {
+                    ::std::option::Option::Some(AwsErrorKind::NotFound)
+                }
+                "ValidationException" => ::std::option::Option::Some(AwsErrorKind::Validation),
+                "InternalServerException" | "InternalServerError" | "InternalError" => {
+                    ::std::option::Option::Some(AwsErrorKind::Internal)
+                }
+                _ => ::std::option::Option::None,
+            }
+        }
+
+        /// Builds the appropriate error variant from a classified kind plus the
+        /// original error's message, service error code, request ID, and source.
+        fn from_aws_error_kind(
+            kind: ::std::option::Option<AwsErrorKind>,
+            message: ::std::string::String,
+            code: ::std::option::Option<::std::string::String>,
+            request_id: ::std::option::Option<::std::string::String>,
+            source: impl ::std::error::Error + Send + Sync + 'static,
+        ) -> $name {
+            let source = ::std::option::Option::Some(
+                ::std::boxed::Box::new(source) as ::std::boxed::Box<dyn ::std::error::Error + Send + Sync>
+            );
+            match kind {
+                ::std::option::Option::Some(AwsErrorKind::Throttled) => $name::Throttled { message, code, request_id, source },
+                ::std::option::Option::Some(AwsErrorKind::Conflict) => $name::Conflict { message, code, request_id, source },
+                ::std::option::Option::Some(AwsErrorKind::AccessDenied) => $name::AccessDenied { message, code, request_id, source },
+                ::std::option::Option::Some(AwsErrorKind::QuotaExceeded) => $name::QuotaExceeded { message, code, request_id, source },
+                ::std::option::Option::Some(AwsErrorKind::NotFound) => $name::NotFound { message, code, request_id, source },
+                ::std::option::Option::Some(AwsErrorKind::Validation) => $name::Validation { message, code, request_id, source },
+                ::std::option::Option::Some(AwsErrorKind::Internal) => $name::Unavailable { message, code, request_id, source },
+                ::std::option::Option::None => $name::Unhandled { message, code, request_id, source },
+            }
+        }
+
+        impl ::std::convert::From<::aws_sdk_dynamodb::Error> for $name {
+            fn from(err: ::aws_sdk_dynamodb::Error) -> Self {
+                use ::aws_smithy_types::error::metadata::ProvideErrorMetadata;
+                use ::aws_types::request_id::RequestId;
+                let kind = classify_aws_error_code(err.code());
+                let message = err.to_string();
+                let code = err.code().map(str::to_string);
+                let request_id = err.request_id().map(str::to_string);
+                from_aws_error_kind(kind, message, code, request_id, err)
+            }
+        }
+
+        impl ::std::convert::From<::aws_sdk_s3::Error> for $name {
+            fn from(err: ::aws_sdk_s3::Error) -> Self {
+                use ::aws_smithy_types::error::metadata::ProvideErrorMetadata;
+                use ::aws_types::request_id::RequestId;
+                let kind = classify_aws_error_code(err.code());
+                let message = err.to_string();
+                let code = err.code().map(str::to_string);
+                let request_id = err.request_id().map(str::to_string);
+                from_aws_error_kind(kind, message, code, request_id, err)
+            }
+        }
+
+        impl<E, R> ::std::convert::From<::aws_smithy_runtime_api::client::result::SdkError<E, R>> for $name
+        where
+            E: ::aws_smithy_types::error::metadata::ProvideErrorMetadata + ::std::error::Error + Send + Sync + 'static,
+            R: ::std::fmt::Debug + Send + Sync + 'static,
+            ::aws_smithy_runtime_api::client::result::SdkError<E, R>: ::aws_types::request_id::RequestId,
+        {
+            fn from(err: ::aws_smithy_runtime_api::client::result::SdkError<E, R>) -> Self {
+                use ::aws_smithy_types::error::metadata::ProvideErrorMetadata;
+                use ::aws_types::request_id::RequestId;
+                let kind = classify_aws_error_code(err.code());
+                let message = err.to_string();
+                let code = err.code().map(str::to_string);
+                let request_id = err.request_id().map(str::to_string);
+                from_aws_error_kind(kind, message, code, request_id, err.into_service_error())
+            }
+        }
+
+        impl $name {
+            /// Converts any AWS `SdkError` into this error type, analogous to
+            /// `SdkError::into_service_error`: a modeled service error becomes
+            /// one of our structured variants, and anything else (an unknown
+            /// error code, a malformed response, or a connection/timeout
+            /// failure) becomes `Unhandled`. This conversion never panics and
+            /// never discards the original error.
+            pub fn from_sdk_error<E, R>(err: ::aws_smithy_runtime_api::client::result::SdkError<E, R>) -> Self
+            where
+                E: ::aws_smithy_types::error::metadata::ProvideErrorMetadata + ::std::error::Error + Send + Sync + 'static,
+                R: ::std::fmt::Debug + Send + Sync + 'static,
+                ::aws_smithy_runtime_api::client::result::SdkError<E, R>: ::aws_types::request_id::RequestId,
+            {
+                err.into()
+            }
+
+            /// Returns the HTTP status code for this error
+            pub fn status_code(&self) -> u16 {
+                match self {
+                    $name::Config(_) => 500,
+                    $name::Validation { .. } => 400,
+                    $name::NotFound { .. } => 404,
+                    $name::Internal(_) => 500,
+                    $name::Throttled { .. } => 429,
+                    $name::Conflict { .. } => 409,
+                    $name::AccessDenied { .. } => 403,
+                    $name::QuotaExceeded { .. } => 429,
+                    $name::Serialization(_) => 400,
+                    $name::Unhandled { .. } => 500,
+                    $name::Unavailable { .. } => 503,
+                }
+            }
+
+            /// Create a validation error from a message
+            pub fn validation(message: impl Into<::std::string::String>) -> Self {
+                $name::Validation {
+                    message: message.into(),
+                    code: ::std::option::Option::None,
+                    request_id: ::std::option::Option::None,
+                    source: ::std::option::Option::None,
+                }
+            }
+
+            /// Create a not-found error from a message
+            pub fn not_found(message: impl Into<::std::string::String>) -> Self {
+                $name::NotFound {
+                    message: message.into(),
+                    code: ::std::option::Option::None,
+                    request_id: ::std::option::Option::None,
+                    source: ::std::option::Option::None,
+                }
+            }
+
+            /// Create an AWS error from a message, for cases not produced by a
+            /// `from_sdk_error`/`From<SdkError<_, _>>` conversion. Folded into
+            /// `Unhandled` since, from the caller's perspective, "a generic
+            /// AWS failure with no source" and "an AWS failure we couldn't
+            /// classify" are the same situation.
+            pub fn aws(message: impl Into<::std::string::String>) -> Self {
+                $name::Unhandled {
+                    message: message.into(),
+                    code: ::std::option::Option::None,
+                    request_id: ::std::option::Option::None,
+                    source: ::std::option::Option::None,
+                }
+            }
+
+            /// Create an AWS error with a source error. See [`Self::aws`].
+            pub fn aws_with_source(
+                message: impl Into<::std::string::String>,
+                source: impl ::std::error::Error + Send + Sync + 'static,
+            ) -> Self {
+                $name::Unhandled {
+                    message: message.into(),
+                    code: ::std::option::Option::None,
+                    request_id: ::std::option::Option::None,
+                    source: ::std::option::Option::Some(::std::boxed::Box::new(source)),
+                }
+            }
+
+            /// The AWS service error code for this error, if one was reported (e.g.
+            /// `"ThrottlingException"`). Useful for correlating with CloudWatch.
+            pub fn code(&self) -> ::std::option::Option<&str> {
+                match self {
+                    $name::Throttled { code, .. }
+                    | $name::Conflict { code, .. }
+                    | $name::AccessDenied { code, .. }
+                    | $name::QuotaExceeded { code, .. }
+                    | $name::Unhandled { code, .. }
+                    | $name::Unavailable { code, .. }
+                    | $name::Validation { code, .. }
+                    | $name::NotFound { code, .. } => code.as_deref(),
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            /// The AWS request ID the service saw for this error, if one was reported.
+            /// Useful for correlating with CloudWatch.
+            pub fn request_id(&self) -> ::std::option::Option<&str> {
+                match self {
+                    $name::Throttled { request_id, .. }
+                    | $name::Conflict { request_id, .. }
+                    | $name::AccessDenied { request_id, .. }
+                    | $name::QuotaExceeded { request_id, .. }
+                    | $name::Unhandled { request_id, .. }
+                    | $name::Unavailable { request_id, .. }
+                    | $name::Validation { request_id, .. }
+                    | $name::NotFound { request_id, .. } => request_id.as_deref(),
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            /// Serializes this error to the JSON body our Lambda handlers return,
+            /// including the AWS error code and request ID when available.
+            pub fn to_response_body(&self) -> ::serde_json::Value {
+                ::serde_json::json!({
+                    "error": self.to_string(),
+                    "code": self.code(),
+                    "request_id": self.request_id(),
+                })
+            }
+
+            /// Emits a `tracing::error!` event for this error with `code` and
+            /// `request_id` as span fields, so a failing request can be grepped
+            /// out of CloudWatch by the AWS request ID the service actually saw.
+            pub fn trace(&self) {
+                ::tracing::error!(
+                    code = self.code(),
+                    request_id = self.request_id(),
+                    status = self.status_code(),
+                    "{}",
+                    self,
+                );
+            }
+
+            /// Whether retrying the operation that produced this error stands a
+            /// chance of succeeding.
+            pub fn retryability(&self) -> Retryability {
+                match self {
+                    $name::Throttled { .. } => Retryability::Retryable,
+                    $name::Unavailable { .. } => Retryability::Retryable,
+                    // Unknown error codes and connection/timeout failures are
+                    // often transient, so give them the benefit of the doubt.
+                    $name::Unhandled { .. } => Retryability::Retryable,
+                    // `Internal` covers deterministic, non-AWS failures (see
+                    // its doc comment) — retrying won't change the outcome.
+                    $name::Internal(_)
+                    | $name::Config(_)
+                    | $name::Validation { .. }
+                    | $name::NotFound { .. }
+                    | $name::Conflict { .. }
+                    | $name::AccessDenied { .. }
+                    | $name::QuotaExceeded { .. }
+                    | $name::Serialization(_) => Retryability::NotRetryable,
+                }
+            }
+
+            /// Shorthand for `self.retryability() == Retryability::Retryable`.
+            pub fn is_retryable(&self) -> bool {
+                self.retryability() == Retryability::Retryable
+            }
+
+            /// Whether this error is an AWS throttling response, which warrants a
+            /// more patient backoff than an ordinary transient failure.
+            pub fn is_throttled(&self) -> bool {
+                matches!(self, $name::Throttled { .. })
+            }
+        }
+
+        impl $crate::retry::RetryableError for $name {
+            fn is_retryable(&self) -> bool {
+                $name::is_retryable(self)
+            }
+
+            fn is_throttled(&self) -> bool {
+                $name::is_throttled(self)
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[derive(::std::fmt::Debug)]
+            struct TestSource;
+
+            impl ::std::fmt::Display for TestSource {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    write!(f, "test source")
+                }
+            }
+
+            impl ::std::error::Error for TestSource {}
+
+            #[test]
+            fn classify_aws_error_code_maps_dynamodb_throttling_to_throttled() {
+                assert!(matches!(
+                    classify_aws_error_code(::std::option::Option::Some("ProvisionedThroughputExceededException")),
+                    ::std::option::Option::Some(AwsErrorKind::Throttled)
+                ));
+            }
+
+            #[test]
+            fn classify_aws_error_code_maps_s3_not_found_codes_to_not_found() {
+                assert!(matches!(
+                    classify_aws_error_code(::std::option::Option::Some("NoSuchKey")),
+                    ::std::option::Option::Some(AwsErrorKind::NotFound)
+                ));
+                assert!(matches!(
+                    classify_aws_error_code(::std::option::Option::Some("NoSuchBucket")),
+                    ::std::option::Option::Some(AwsErrorKind::NotFound)
+                ));
+            }
+
+            #[test]
+            fn classify_aws_error_code_maps_s3_access_denied_and_internal_error() {
+                assert!(matches!(
+                    classify_aws_error_code(::std::option::Option::Some("AccessDenied")),
+                    ::std::option::Option::Some(AwsErrorKind::AccessDenied)
+                ));
+                assert!(matches!(
+                    classify_aws_error_code(::std::option::Option::Some("InternalError")),
+                    ::std::option::Option::Some(AwsErrorKind::Internal)
+                ));
+            }
+
+            #[test]
+            fn classify_aws_error_code_returns_none_for_an_unrecognized_code() {
+                assert!(classify_aws_error_code(::std::option::Option::Some("SomeBrandNewException")).is_none());
+            }
+
+            #[test]
+            fn from_aws_error_kind_builds_a_retryable_throttled_error_with_metadata() {
+                let err = from_aws_error_kind(
+                    classify_aws_error_code(::std::option::Option::Some("ProvisionedThroughputExceededException")),
+                    "slow down".to_string(),
+                    ::std::option::Option::Some("ProvisionedThroughputExceededException".to_string()),
+                    ::std::option::Option::Some("req-123".to_string()),
+                    TestSource,
+                );
+                assert_eq!(err.status_code(), 429);
+                assert!(err.is_retryable());
+                assert!(err.is_throttled());
+                assert_eq!(err.code(), ::std::option::Option::Some("ProvisionedThroughputExceededException"));
+                assert_eq!(err.request_id(), ::std::option::Option::Some("req-123"));
+            }
+
+            #[test]
+            fn from_aws_error_kind_builds_a_not_found_error_and_keeps_its_metadata() {
+                let err = from_aws_error_kind(
+                    classify_aws_error_code(::std::option::Option::Some("NoSuchKey")),
+                    "no such key".to_string(),
+                    ::std::option::Option::Some("NoSuchKey".to_string()),
+                    ::std::option::Option::Some("req-456".to_string()),
+                    TestSource,
+                );
+                assert_eq!(err.status_code(), 404);
+                assert!(!err.is_retryable());
+                assert_eq!(err.code(), ::std::option::Option::Some("NoSuchKey"));
+                assert_eq!(err.request_id(), ::std::option::Option::Some("req-456"));
+            }
+
+            #[test]
+            fn from_aws_error_kind_falls_back_to_unhandled_for_an_unrecognized_code() {
+                let err = from_aws_error_kind(
+                    classify_aws_error_code(::std::option::Option::Some("SomeBrandNewException")),
+                    "mystery failure".to_string(),
+                    ::std::option::Option::Some("SomeBrandNewException".to_string()),
+                    ::std::option::Option::None,
+                    TestSource,
+                );
+                assert_eq!(err.status_code(), 500);
+                assert!(err.is_retryable());
+                assert_eq!(err.code(), ::std::option::Option::Some("SomeBrandNewException"));
+                assert_eq!(err.request_id(), ::std::option::Option::None);
+            }
+
+            #[test]
+            fn internal_server_exception_is_retryable_via_the_unavailable_variant() {
+                let err = from_aws_error_kind(
+                    classify_aws_error_code(::std::option::Option::Some("InternalServerException")),
+                    "internal failure".to_string(),
+                    ::std::option::Option::Some("InternalServerException".to_string()),
+                    ::std::option::Option::None,
+                    TestSource,
+                );
+                assert_eq!(err.status_code(), 503);
+                assert!(err.is_retryable());
+            }
+
+            #[test]
+            fn deterministic_internal_error_is_not_retryable() {
+                let err = $name::Internal("bad config value".to_string());
+                assert_eq!(err.status_code(), 500);
+                assert!(!err.is_retryable());
+            }
+
+            #[test]
+            fn validation_and_not_found_constructors_carry_no_aws_metadata() {
+                let validation = $name::validation("missing field");
+                assert_eq!(validation.status_code(), 400);
+                assert_eq!(validation.code(), ::std::option::Option::None);
+                assert_eq!(validation.request_id(), ::std::option::Option::None);
+
+                let not_found = $name::not_found("no such record");
+                assert_eq!(not_found.status_code(), 404);
+                assert_eq!(not_found.code(), ::std::option::Option::None);
+            }
+        }
+    };
+}
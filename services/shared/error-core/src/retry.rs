@@ -0,0 +1,183 @@
+//! Retry helper with full-jitter exponential backoff.
+//!
+//! Lives here rather than in each backend's core crate for the same reason
+//! the error enum itself does (see the crate-level docs): the retry loop
+//! doesn't depend on which service error type is plugged in, only on
+//! [`RetryableError`], which [`crate::define_service_error!`] implements for
+//! every generated type.
+
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+
+/// The retry-classification surface a service error type must provide for
+/// [`retry`] to drive its backoff decisions.
+pub trait RetryableError {
+    /// Whether retrying the operation that produced this error stands a
+    /// chance of succeeding.
+    fn is_retryable(&self) -> bool;
+
+    /// Whether this error is an AWS throttling response, which warrants a
+    /// more patient backoff than an ordinary transient failure.
+    fn is_throttled(&self) -> bool;
+}
+
+/// Tuning knobs for [`retry`].
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Base delay used for ordinary transient errors.
+    pub base: Duration,
+    /// Base delay used when the error is an AWS throttling response; should
+    /// be higher than `base` so we back off more patiently under load.
+    pub throttle_base: Duration,
+    /// Upper bound on the backoff delay for any single attempt.
+    pub cap: Duration,
+    /// Total number of attempts (including the first), after which the last
+    /// error is returned.
+    pub max_attempts: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            base: Duration::from_millis(50),
+            throttle_base: Duration::from_millis(200),
+            cap: Duration::from_secs(20),
+            max_attempts: 5,
+        }
+    }
+}
+
+/// Runs `operation` with full-jitter exponential backoff, per
+/// <https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/>.
+///
+/// On attempt `n` (0-indexed), the delay before the next try is chosen
+/// uniformly from `[0, min(cap, base * 2^n))`, where `base` is
+/// `policy.throttle_base` if the failing error was a throttling error and
+/// `policy.base` otherwise. Stops and returns immediately on a
+/// non-retryable error, or after `policy.max_attempts` attempts.
+pub async fn retry<T, E, F, Fut>(policy: RetryPolicy, mut operation: F) -> Result<T, E>
+where
+    E: RetryableError,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt: u32 = 0;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                let out_of_attempts = attempt + 1 >= policy.max_attempts;
+                if !err.is_retryable() || out_of_attempts {
+                    return Err(err);
+                }
+                let base = if err.is_throttled() {
+                    policy.throttle_base
+                } else {
+                    policy.base
+                };
+                tokio::time::sleep(full_jitter_delay(base, policy.cap, attempt)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Picks a full-jitter backoff delay for the given attempt number.
+fn full_jitter_delay(base: Duration, cap: Duration, attempt: u32) -> Duration {
+    let exponential = base
+        .as_millis()
+        .saturating_mul(1u128 << attempt.min(63));
+    let upper_ms = exponential.min(cap.as_millis()).max(1);
+    let jittered_ms = rand::thread_rng().gen_range(0..upper_ms);
+    Duration::from_millis(jittered_ms as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    // A throwaway service error type, generated by the same macro
+    // `StudioError`/`GlitchError` use, so these tests exercise the real
+    // `RetryableError` impl rather than a hand-rolled stand-in.
+    crate::define_service_error!(TestError);
+
+    #[test]
+    fn full_jitter_delay_never_exceeds_cap() {
+        let cap = Duration::from_millis(100);
+        for attempt in 0..10 {
+            let delay = full_jitter_delay(Duration::from_millis(50), cap, attempt);
+            assert!(delay <= cap, "attempt {attempt} produced {delay:?} > cap {cap:?}");
+        }
+    }
+
+    #[test]
+    fn full_jitter_delay_is_bounded_by_exponential_growth_before_the_cap() {
+        let delay = full_jitter_delay(Duration::from_millis(10), Duration::from_secs(60), 2);
+        // base * 2^2 = 40ms
+        assert!(delay <= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn retry_returns_first_success_without_retrying() {
+        let calls = AtomicU32::new(0);
+        let policy = RetryPolicy {
+            base: Duration::from_millis(1),
+            throttle_base: Duration::from_millis(1),
+            cap: Duration::from_millis(5),
+            max_attempts: 3,
+        };
+
+        let result = retry(policy, || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Ok::<_, TestError>(42) }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn retry_stops_immediately_on_a_non_retryable_error() {
+        let calls = AtomicU32::new(0);
+        let policy = RetryPolicy {
+            base: Duration::from_millis(1),
+            throttle_base: Duration::from_millis(1),
+            cap: Duration::from_millis(5),
+            max_attempts: 5,
+        };
+
+        let result = retry(policy, || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err::<(), _>(TestError::validation("bad input")) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn retry_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let policy = RetryPolicy {
+            base: Duration::from_millis(1),
+            throttle_base: Duration::from_millis(1),
+            cap: Duration::from_millis(5),
+            max_attempts: 3,
+        };
+
+        let result = retry(policy, || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err::<(), _>(TestError::aws("transient")) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
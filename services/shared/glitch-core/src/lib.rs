@@ -6,6 +6,7 @@
 //! used across multiple Lambda functions.
 
 pub mod error;
+pub mod retry;
 
 pub use error::GlitchError;
 
@@ -0,0 +1,7 @@
+//! Retry helper with full-jitter exponential backoff.
+//!
+//! Re-exports [`error_core::retry`], parameterized over [`crate::GlitchError`]
+//! via the `RetryableError` impl `error_core::define_service_error!` generates
+//! for it. See that module for the backoff math and its tests.
+
+pub use error_core::retry::{retry, RetryPolicy};